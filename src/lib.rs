@@ -1,80 +1,344 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Collects every `.md` file reachable from `path`, sorted by path so the
+/// result (and anything derived from it, like a generated report) is
+/// reproducible regardless of the OS's directory iteration order.
+///
+/// If `path` already points at a single file, that file is returned as-is.
+/// Otherwise `path` is walked recursively: modeled on rust-analyzer's
+/// sourcegen `list_files`, a worklist of directories is popped until empty,
+/// pushing any child directories back onto the list and collecting markdown
+/// files as they're found. Entries whose file name starts with `.` are
+/// skipped entirely.
+fn list_files(path: &Path) -> Vec<PathBuf> {
+    if path.is_file() {
+        return vec![path.to_path_buf()];
+    }
 
-pub fn get_sentences(path: PathBuf) -> Vec<Vec<String>> {
     let mut files = Vec::new();
-    for entry in path.read_dir().unwrap() {
-        let path = entry.unwrap().path();
-        if path.is_file() && path.extension().unwrap() == "md" {
-            files.push(path);
+    let mut worklist = vec![path.to_path_buf()];
+
+    while let Some(dir) = worklist.pop() {
+        for entry in dir.read_dir().unwrap() {
+            let entry_path = entry.unwrap().path();
+            let file_name = entry_path.file_name().unwrap().to_string_lossy();
+            if file_name.starts_with('.') {
+                continue;
+            }
+            if entry_path.is_dir() {
+                worklist.push(entry_path);
+            } else if entry_path.extension().is_some_and(|ext| ext == "md") {
+                files.push(entry_path);
+            }
         }
     }
-    let mut contents = Vec::new();
-    for file in files {
-        let content = std::fs::read_to_string(file).unwrap();
-        contents.push(content);
+
+    files.sort();
+    files
+}
+
+/// A single sentence extracted from an [`Article`], together with the place
+/// it came from, analogous to rust-analyzer's `CommentBlock` tracking the
+/// line a doc comment started on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sentence {
+    pub text: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// A markdown file and the sentences extracted from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Article {
+    pub path: PathBuf,
+    pub metadata: ArticleMetadata,
+    pub sentences: Vec<Sentence>,
+}
+
+/// The YAML front matter of an `Article`, as found between the `---` fences
+/// Jekyll/Hugo expect at the top of a post.
+///
+/// `extra` holds whatever fields aren't `title`/`date`/`tags` as permissive
+/// [`serde_yaml::Value`]s, since Jekyll/Hugo posts commonly carry booleans,
+/// numbers, lists, and nested maps (`published`, `categories`, `author:`,
+/// ...) there; typing it as `String` would make any one of those fail to
+/// deserialize and, with it, the whole `ArticleMetadata`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Default)]
+#[serde(default)]
+pub struct ArticleMetadata {
+    pub title: Option<String>,
+    pub date: Option<String>,
+    pub tags: Vec<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_yaml::Value>,
+}
+
+/// Abbreviations whose trailing `.` must not be treated as a sentence
+/// boundary, e.g. "the `e.g.` in this sentence" should stay one sentence.
+const ABBREVIATIONS: &[&str] = &["e.g", "i.e", "vs", "Mr", "Dr", "etc", "cf"];
+
+/// Returns whether `text` ends with one of the known [`ABBREVIATIONS`],
+/// ignoring the trailing `.` that's about to be appended.
+fn ends_with_abbreviation(text: &str) -> bool {
+    let token = text
+        .trim_end()
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or("");
+    ABBREVIATIONS.contains(&token)
+}
+
+/// A rule-based sentence boundary scanner.
+///
+/// Lines are fed in one at a time and accumulated into `sentence`; completed
+/// sentences are pushed out as soon as a real boundary is found. `.`, `!`
+/// and `?` are candidate boundaries, but a `.` is suppressed when it sits
+/// between two digits (a decimal), when it immediately follows a known
+/// abbreviation, or when it's part of an `...` ellipsis that isn't both
+/// followed by whitespace and an uppercase letter or opening quote. Inline
+/// `` `code` `` spans never contain a boundary, tracked the same way fenced
+/// code blocks are tracked by the caller.
+struct Segmenter {
+    sentence: String,
+    sentence_line: usize,
+    in_backtick: bool,
+}
+
+impl Segmenter {
+    fn new() -> Self {
+        Segmenter {
+            sentence: String::new(),
+            sentence_line: 0,
+            in_backtick: false,
+        }
+    }
+
+    /// Pushes whatever has accumulated so far as a finished sentence, e.g.
+    /// when a fenced code block closes.
+    fn flush(&mut self, file: &Path, sentences: &mut Vec<Sentence>) {
+        let text = self.sentence.trim();
+        if !text.is_empty() {
+            sentences.push(Sentence {
+                text: text.to_string(),
+                file: file.to_path_buf(),
+                line: self.sentence_line,
+            });
+        }
+        self.sentence.clear();
+    }
+
+    fn feed_line(
+        &mut self,
+        line: &str,
+        line_no: usize,
+        in_code_block: bool,
+        file: &Path,
+        sentences: &mut Vec<Sentence>,
+    ) {
+        if in_code_block {
+            if self.sentence.is_empty() {
+                self.sentence_line = line_no;
+            }
+            self.sentence.push_str(line);
+            self.sentence.push('\n');
+            return;
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+
+            if self.sentence.trim_start().is_empty() && !c.is_whitespace() {
+                self.sentence_line = line_no;
+            }
+
+            if c == '`' {
+                self.in_backtick = !self.in_backtick;
+                self.sentence.push(c);
+                i += 1;
+                continue;
+            }
+
+            if self.in_backtick || !matches!(c, '.' | '!' | '?') {
+                self.sentence.push(c);
+                i += 1;
+                continue;
+            }
+
+            if c == '.' {
+                let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+                let next = chars.get(i + 1).copied();
+
+                if prev.is_some_and(|p| p.is_ascii_digit())
+                    && next.is_some_and(|n| n.is_ascii_digit())
+                {
+                    self.sentence.push(c);
+                    i += 1;
+                    continue;
+                }
+
+                let mut run_end = i;
+                while run_end + 1 < chars.len() && chars[run_end + 1] == '.' {
+                    run_end += 1;
+                }
+                if run_end > i {
+                    self.sentence.push_str(&".".repeat(run_end - i + 1));
+                    let after = chars.get(run_end + 1).copied();
+                    let after2 = chars.get(run_end + 2).copied();
+                    let ends_sentence = after.is_some_and(|a| a.is_whitespace())
+                        && after2.is_some_and(|a| a.is_uppercase() || a == '"' || a == '\u{201c}');
+                    i = run_end + 1;
+                    if ends_sentence {
+                        self.flush(file, sentences);
+                    }
+                    continue;
+                }
+
+                if ends_with_abbreviation(&self.sentence) {
+                    self.sentence.push(c);
+                    i += 1;
+                    continue;
+                }
+            }
+
+            let next = chars.get(i + 1).copied();
+            self.sentence.push(c);
+            i += 1;
+            if next.is_none_or(|n| n.is_whitespace()) {
+                self.flush(file, sentences);
+            }
+        }
+
+        if !self.sentence.is_empty() {
+            self.sentence.push(' ');
+        }
     }
+}
+
+/// Where we are with respect to a file's YAML front matter. Only a fence
+/// that opens on the first non-blank line of the file counts; once the
+/// front matter has been closed, a later `---` is just a Markdown
+/// horizontal rule, which (like a heading) carries no prose and is
+/// skipped rather than fed to the segmenter.
+#[derive(PartialEq, Eq)]
+enum Preamble {
+    NotStarted,
+    Open,
+    Closed,
+}
 
+pub fn get_sentences(path: PathBuf) -> Vec<Article> {
+    let files = list_files(&path);
     let mut parsed = Vec::new();
 
-    for article in contents {
+    for file in files {
+        let content = std::fs::read_to_string(&file).unwrap();
         let mut sentences = Vec::new();
-        let mut sentence = String::new();
+        let mut segmenter = Segmenter::new();
         let mut in_code_block = false;
-        let mut in_preamble = false;
-        for line in article.lines() {
+        let mut preamble_state = Preamble::NotStarted;
+        let mut preamble = String::new();
+        let mut seen_content = false;
+        for (line_no, line) in content.lines().enumerate() {
+            let line_no = line_no + 1;
             if line.is_empty() {
                 continue;
             }
             if line.starts_with('#') {
+                seen_content = true;
                 continue;
             }
             if line.starts_with("---") {
-                in_preamble = !in_preamble;
-                continue;
-            }
-            if in_preamble {
-                continue;
-            }
-            if line.starts_with("```") {
-                if in_code_block {
-                    sentences.push(sentence);
-                    sentence = String::new();
-                }
-                in_code_block = !in_code_block;
-                continue;
-            }
-            if line.contains(". ") && !in_code_block {
-                let line = line.split(". ");
-                let count = line.clone().count();
-                for (idx, sentence_part) in line.enumerate() {
-                    if sentence_part.is_empty() {
+                match preamble_state {
+                    Preamble::NotStarted if !seen_content => {
+                        preamble_state = Preamble::Open;
+                        seen_content = true;
                         continue;
                     }
-                    sentence.push_str(sentence_part);
-                    if idx < count - 1 {
-                        sentence.push('.');
-                        sentences.push(sentence);
-                        sentence = String::new();
-                    } else {
-                        sentence.push(' ');
+                    Preamble::Open => {
+                        preamble_state = Preamble::Closed;
+                        continue;
+                    }
+                    _ => {
+                        // A horizontal rule in the body, not a front-matter fence.
+                        seen_content = true;
+                        continue;
                     }
                 }
+            }
+            seen_content = true;
+            if preamble_state == Preamble::Open {
+                preamble.push_str(line);
+                preamble.push('\n');
                 continue;
             }
-            if line.ends_with('.') {
-                sentence.push_str(line);
-                sentences.push(sentence);
-                sentence = String::new();
-            } else {
-                sentence.push_str(line);
+            if line.starts_with("```") {
                 if in_code_block {
-                    sentence.push('\n');
-                } else {
-                    sentence.push(' ');
+                    segmenter.flush(&file, &mut sentences);
                 }
+                in_code_block = !in_code_block;
+                continue;
             }
+            segmenter.feed_line(line, line_no, in_code_block, &file, &mut sentences);
         }
-        parsed.push(sentences);
+        segmenter.flush(&file, &mut sentences);
+        let metadata: ArticleMetadata = serde_yaml::from_str(&preamble).unwrap();
+        parsed.push(Article {
+            path: file,
+            metadata,
+            sentences,
+        });
     }
     parsed
 }
+
+/// Whether [`update`] should write the generated file or merely check that
+/// it's still up to date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Overwrite,
+    Verify,
+}
+
+/// Renders the sentences extracted from `articles` as one line per
+/// sentence, with a blank line after each article. A sentence taken from a
+/// fenced code block keeps its line breaks and so spans several lines.
+pub fn render_report(articles: &[Article]) -> String {
+    let mut report = String::new();
+    for article in articles {
+        for sentence in &article.sentences {
+            report.push_str(&sentence.text);
+            report.push('\n');
+        }
+        report.push('\n');
+    }
+    report
+}
+
+/// Writes `contents` to `dst`, but only if it differs from what's already
+/// there, mirroring rust-analyzer's `codegen::update`. In [`Mode::Verify`]
+/// nothing is written; instead an error is returned when `dst` is missing
+/// or out of date, so the extraction can be wired into a CI check that
+/// fails when committed output drifts from the source markdown.
+pub fn update(dst: &Path, contents: &str, mode: Mode) -> std::io::Result<()> {
+    match (std::fs::read_to_string(dst), mode) {
+        (Ok(old), _) if old == contents => Ok(()),
+        (_, Mode::Verify) => Err(std::io::Error::other(format!(
+            "`{}` is not up to date, run in `Mode::Overwrite` to regenerate it",
+            dst.display()
+        ))),
+        (_, Mode::Overwrite) => std::fs::write(dst, contents),
+    }
+}
+
+/// Extracts the sentences found under `src` and writes the report to `dst`,
+/// respecting `mode` (see [`update`]).
+pub fn generate_report(src: PathBuf, dst: &Path, mode: Mode) -> std::io::Result<()> {
+    let articles = get_sentences(src);
+    let report = render_report(&articles);
+    update(dst, &report, mode)
+}
@@ -1,5 +1,18 @@
+use std::io::Write;
+use std::path::PathBuf;
+
 use sentence_extractor::get_sentences;
 
+/// Writes `content` to a uniquely named markdown file under the system
+/// temp dir, for tests that need a real file but don't want to depend on
+/// the `./fixtures` tree.
+fn write_temp_md(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("sentence_extractor_{name}.md"));
+    let mut file = std::fs::File::create(&path).unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    path
+}
+
 #[test]
 fn correct_articles() {
     let articles = get_sentences("./fixtures".into());
@@ -9,12 +22,136 @@ fn correct_articles() {
 #[test]
 fn first_sentence_correct() {
     let articles = get_sentences("./fixtures".into());
-    assert_eq!(articles[0][0].as_str(), "The following piece of code takes a `PathBuf` and extracts the file name, eventually converting it to an _owned_ `String`.")
+    assert_eq!(articles[0].sentences[0].text.as_str(), "The following piece of code takes a `PathBuf` and extracts the file name, eventually converting it to an _owned_ `String`.")
 }
 
 #[test]
 fn only_article() {
     let article =
         get_sentences("./fixtures/2022-05-11-typescript-iterating-over-objects.md".into());
-    assert_eq!(article[0][0].as_str(), "There is rarely a head-scratcher in TypeScript as prominent as trying to access an object property via iterating through its keys.")
+    assert_eq!(article[0].sentences[0].text.as_str(), "There is rarely a head-scratcher in TypeScript as prominent as trying to access an object property via iterating through its keys.")
+}
+
+#[test]
+fn abbreviations_do_not_end_a_sentence() {
+    let path = write_temp_md(
+        "abbreviations",
+        "We discussed several languages, e.g. Rust and Go, vs. older ones like C. Dr. Smith agreed.",
+    );
+    let articles = get_sentences(path.clone());
+    let sentences: Vec<&str> = articles[0]
+        .sentences
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect();
+    assert_eq!(
+        sentences,
+        vec![
+            "We discussed several languages, e.g. Rust and Go, vs. older ones like C.",
+            "Dr. Smith agreed.",
+        ]
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn decimal_points_do_not_end_a_sentence() {
+    let path = write_temp_md("decimal", "Version 1.0.24 was released today.");
+    let articles = get_sentences(path.clone());
+    assert_eq!(
+        articles[0].sentences[0].text.as_str(),
+        "Version 1.0.24 was released today."
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn ellipsis_followed_by_uppercase_ends_a_sentence() {
+    let path = write_temp_md("ellipsis-upper", "Wait... What happened?");
+    let articles = get_sentences(path.clone());
+    let sentences: Vec<&str> = articles[0]
+        .sentences
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect();
+    assert_eq!(sentences, vec!["Wait...", "What happened?"]);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn ellipsis_followed_by_lowercase_does_not_end_a_sentence() {
+    let path = write_temp_md("ellipsis-lower", "Wait... what happened?");
+    let articles = get_sentences(path.clone());
+    assert_eq!(
+        articles[0].sentences[0].text.as_str(),
+        "Wait... what happened?"
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn period_inside_inline_code_does_not_end_a_sentence() {
+    let path = write_temp_md("inline-code", "See the `example.txt` file for details.");
+    let articles = get_sentences(path.clone());
+    assert_eq!(
+        articles[0].sentences[0].text.as_str(),
+        "See the `example.txt` file for details."
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn sentence_without_trailing_punctuation_is_not_dropped() {
+    let path = write_temp_md("no-trailing-punctuation", "Sentence A.\n\nSentence B");
+    let articles = get_sentences(path.clone());
+    let sentences: Vec<&str> = articles[0]
+        .sentences
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect();
+    assert_eq!(sentences, vec!["Sentence A.", "Sentence B"]);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn non_string_front_matter_fields_do_not_wipe_out_the_rest_of_the_metadata() {
+    let path = write_temp_md(
+        "jekyll-front-matter",
+        "---\ntitle: Jekyll Post\npublished: true\ncategories:\n  - a\n  - b\n---\n\nHello world.\n",
+    );
+    let articles = get_sentences(path.clone());
+    assert_eq!(articles[0].metadata.title.as_deref(), Some("Jekyll Post"));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn leading_blank_line_does_not_prevent_front_matter_from_opening() {
+    let path = write_temp_md(
+        "leading-blank-line",
+        "\n---\ntitle: Leading Blank Test\n---\n\nHello world.\n",
+    );
+    let articles = get_sentences(path.clone());
+    assert_eq!(
+        articles[0].metadata.title.as_deref(),
+        Some("Leading Blank Test")
+    );
+    assert_eq!(articles[0].sentences[0].text.as_str(), "Hello world.");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn horizontal_rule_in_body_does_not_reopen_front_matter() {
+    let path = write_temp_md(
+        "horizontal-rule",
+        "---\ntitle: X\n---\n\nSentence A.\n\n---\n\nSentence B.",
+    );
+    let articles = get_sentences(path.clone());
+    assert_eq!(articles[0].metadata.title.as_deref(), Some("X"));
+    let sentences: Vec<&str> = articles[0]
+        .sentences
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect();
+    assert_eq!(sentences, vec!["Sentence A.", "Sentence B."]);
+    std::fs::remove_file(path).unwrap();
 }
@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use sentence_extractor::{update, Mode};
+
+/// A uniquely named file under the system temp dir, removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("sentence_extractor_report_{name}"));
+        let _ = std::fs::remove_file(&path);
+        TempFile(path)
+    }
+
+    fn path(&self) -> &std::path::Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn overwrite_writes_missing_file() {
+    let dst = TempFile::new("overwrite_writes_missing_file");
+    update(dst.path(), "hello\n", Mode::Overwrite).unwrap();
+    assert_eq!(std::fs::read_to_string(dst.path()).unwrap(), "hello\n");
+}
+
+#[test]
+fn overwrite_is_a_no_op_when_content_is_unchanged() {
+    let dst = TempFile::new("overwrite_is_a_no_op_when_content_is_unchanged");
+    update(dst.path(), "hello\n", Mode::Overwrite).unwrap();
+
+    assert!(update(dst.path(), "hello\n", Mode::Overwrite).is_ok());
+    assert_eq!(std::fs::read_to_string(dst.path()).unwrap(), "hello\n");
+}
+
+#[test]
+fn verify_fails_when_the_file_is_missing() {
+    let dst = TempFile::new("verify_fails_when_the_file_is_missing");
+    assert!(update(dst.path(), "hello\n", Mode::Verify).is_err());
+}
+
+#[test]
+fn verify_fails_when_the_file_is_stale() {
+    let dst = TempFile::new("verify_fails_when_the_file_is_stale");
+    update(dst.path(), "hello\n", Mode::Overwrite).unwrap();
+    assert!(update(dst.path(), "goodbye\n", Mode::Verify).is_err());
+}
+
+#[test]
+fn verify_succeeds_when_the_file_is_up_to_date() {
+    let dst = TempFile::new("verify_succeeds_when_the_file_is_up_to_date");
+    update(dst.path(), "hello\n", Mode::Overwrite).unwrap();
+    assert!(update(dst.path(), "hello\n", Mode::Verify).is_ok());
+}